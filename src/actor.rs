@@ -10,14 +10,36 @@
 //
 //! cooper
 
+use crate::registry::{Broker, Handle, Registry};
 use async_channel::{self as channel, Receiver, Sender};
 use futures::future::BoxFuture;
+use futures::FutureExt;
 use std::fmt::Debug;
 use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// The actor function signature
 pub type BoxedActorFn<S> = Box<dyn for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()> + Send>;
 
+/// Errors returned by the `try_*` family of actor methods, in place of the
+/// panics or silently dropped requests that the plain `cast`/`call` fall
+/// back to for ergonomics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActorError {
+    /// The mailbox is at capacity (see [`Actor::with_capacity`]).
+    Full,
+    /// The mailbox is closed, so the request couldn't be queued.
+    MailboxClosed,
+    /// The request was queued, but the actor stopped before responding.
+    Stopped,
+    /// The request was queued and run, but its closure didn't produce a
+    /// response.
+    NoResponse,
+}
+
 /// Message type for the Actor.
 ///
 /// This wraps an async function type that takes a mutable reference to a
@@ -34,15 +56,40 @@ struct Message<S> {
 /// internal state. Each request runs to completion, atomically, in the
 /// order received, and thus tasks do not need to lock or protect the state
 /// for access.
-#[derive(Clone)]
 pub struct Actor<S>
 where
     S: Send + 'static,
 {
     /// The channel to send requests to the actor's processor task.
     tx: Sender<Message<S>>,
+    /// The runtime's join handle for the processor task, so it can be
+    /// awaited on shutdown. Shared across clones since only one of them
+    /// needs to claim it.
+    handle: Arc<Mutex<Option<ActorTask<()>>>>,
+}
+
+// Hand-written instead of `#[derive(Clone)]`: the derive adds an implicit
+// `S: Clone` bound to the generated impl, even though cloning an `Actor`
+// only clones its handle (`tx`/`handle`), never `S` itself. That bound
+// would wrongly reject actors whose state isn't `Clone`.
+impl<S> Clone for Actor<S>
+where
+    S: Send + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            handle: self.handle.clone(),
+        }
+    }
 }
 
+#[cfg(not(feature = "tokio"))]
+type ActorTask<T> = smol::Task<T>;
+
+#[cfg(feature = "tokio")]
+type ActorTask<T> = tokio::task::JoinHandle<T>;
+
 #[cfg(not(feature = "tokio"))]
 fn spawn<F>(future: F)
 where
@@ -61,31 +108,362 @@ where
     tokio::spawn(future);
 }
 
+#[cfg(not(feature = "tokio"))]
+fn spawn_handle<F>(future: F) -> ActorTask<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    smol::spawn(future)
+}
+
+#[cfg(feature = "tokio")]
+fn spawn_handle<F>(future: F) -> ActorTask<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(future)
+}
+
+#[cfg(not(feature = "tokio"))]
+async fn join(handle: ActorTask<()>) {
+    handle.await;
+}
+
+#[cfg(feature = "tokio")]
+async fn join(handle: ActorTask<()>) {
+    let _ = handle.await;
+}
+
+#[cfg(not(feature = "tokio"))]
+async fn sleep(dur: Duration) {
+    smol::Timer::after(dur).await;
+}
+
+#[cfg(feature = "tokio")]
+async fn sleep(dur: Duration) {
+    tokio::time::sleep(dur).await;
+}
+
+#[cfg(not(feature = "tokio"))]
+async fn run_interval<S, F>(actor: Actor<S>, period: Duration, f: F, cancelled: Arc<AtomicBool>)
+where
+    S: Send + 'static,
+    F: for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()> + Clone + Send + 'static,
+{
+    use futures::StreamExt;
+
+    let mut timer = smol::Timer::interval(period);
+    while timer.next().await.is_some() {
+        if cancelled.load(Ordering::Acquire) {
+            break;
+        }
+        actor.cast(f.clone());
+    }
+}
+
+#[cfg(feature = "tokio")]
+async fn run_interval<S, F>(actor: Actor<S>, period: Duration, f: F, cancelled: Arc<AtomicBool>)
+where
+    S: Send + 'static,
+    F: for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()> + Clone + Send + 'static,
+{
+    let mut ticker = tokio::time::interval(period);
+    loop {
+        ticker.tick().await;
+        if cancelled.load(Ordering::Acquire) {
+            break;
+        }
+        actor.cast(f.clone());
+    }
+}
+
+/// A handle to a timer task started by [`Actor::send_interval`].
+///
+/// Dropping the handle, or calling [`cancel`](Self::cancel) explicitly,
+/// stops the timer so it no longer casts into the actor.
+pub struct IntervalHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl IntervalHandle {
+    /// Stops the timer task.
+    pub fn cancel(self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+}
+
+impl Drop for IntervalHandle {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+}
+
+/// A restart strategy for a [`new_supervised`](Actor::new_supervised) actor.
+///
+/// This governs what the processor does after the user's closure panics
+/// while handling a message.
+#[derive(Clone, Debug)]
+pub enum RestartPolicy {
+    /// Never restart. A panic stops the actor for good.
+    Never,
+    /// Always restart, rebuilding the state from the factory.
+    Always,
+    /// Restart on a panic, keeping whatever state survived it, rather than
+    /// rebuilding from the factory.
+    OnPanic,
+    /// Restart on a panic, but back off exponentially between attempts,
+    /// starting at `base` and capping at `max`.
+    ExponentialBackoff { base: Duration, max: Duration },
+}
+
+/// A lifecycle event emitted by a supervised actor.
+///
+/// These are broadcast so that callers can observe the health of a
+/// long-lived actor without needing to wrap every `cast`/`call`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActorEvent {
+    /// The actor's processor has started.
+    Started,
+    /// The processor panicked and has been restarted.
+    Restarted,
+    /// The processor has stopped for good; no more events will follow.
+    Stopped,
+    /// The processor panicked while handling a message.
+    Panicked,
+}
+
 impl<S> Actor<S>
 where
     S: Send + 'static,
 {
     /// Creates a new actor from an initial state.
+    ///
+    /// The actor's mailbox is unbounded, so a producer that outpaces the
+    /// actor can grow it without limit. Use [`with_capacity`](Self::with_capacity)
+    /// to bound it instead.
     pub fn new(state: S) -> Self {
         let (tx, rx) = channel::unbounded();
+        Self::spawn_with(state, tx, rx)
+    }
+
+    /// Creates a new actor from an initial state, with a bounded mailbox.
+    ///
+    /// Once `cap` requests are queued, `cast` drops further requests and
+    /// `cast_async`/`call` suspend until the actor drains the backlog.
+    pub fn with_capacity(state: S, cap: usize) -> Self {
+        let (tx, rx) = channel::bounded(cap);
+        Self::spawn_with(state, tx, rx)
+    }
 
-        // TODO: Stash the handle somewhere?
-        //  Perhaps make a registry of running actors?
-        spawn(async move { Self::run(state, rx).await });
+    fn spawn_with(state: S, tx: Sender<Message<S>>, rx: Receiver<Message<S>>) -> Self {
+        let handle = spawn_handle(async move { Self::run(state, rx).await });
 
-        Self { tx }
+        Self {
+            tx,
+            handle: Arc::new(Mutex::new(Some(handle))),
+        }
+    }
+
+    /// Wraps a `cast`-style closure into a queueable message.
+    fn wrap<F>(f: F) -> Message<S>
+    where
+        F: for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()>,
+        F: 'static + Send,
+    {
+        Message {
+            func: Box::new(move |state| {
+                Box::pin(async move {
+                    f(state).await;
+                })
+            }),
+        }
     }
 
     /// The actor's command processor.
     ///
-    /// This runs each request for the actor to completion before
-    /// running the next one.
+    /// This runs each request for the actor to completion before running
+    /// the next one. When the mailbox is closed, already-queued requests
+    /// are drained and run before the loop - and the task - exits.
     async fn run(mut state: S, rx: Receiver<Message<S>>) {
         while let Ok(msg) = rx.recv().await {
             (msg.func)(&mut state).await;
         }
     }
 
+    /// Creates a new actor that drains its mailbox in batches instead of
+    /// one request at a time.
+    ///
+    /// This amortizes wakeups and timer overhead for bursty producers
+    /// (metrics, log aggregation), while still running each request to
+    /// completion in exact mailbox order - `flush`/`call` observe the
+    /// same ordering guarantees as the default processor. The processor
+    /// drains up to `max_batch` already-queued requests back-to-back,
+    /// then sleeps until the next `interval` tick before draining again.
+    pub fn with_throttle(state: S, interval: Duration, max_batch: usize) -> Self {
+        let (tx, rx) = channel::unbounded();
+        let handle =
+            spawn_handle(async move { Self::run_throttled(state, rx, interval, max_batch).await });
+
+        Self {
+            tx,
+            handle: Arc::new(Mutex::new(Some(handle))),
+        }
+    }
+
+    /// The throttled, batch-draining variant of the command processor.
+    async fn run_throttled(
+        mut state: S,
+        rx: Receiver<Message<S>>,
+        interval: Duration,
+        max_batch: usize,
+    ) {
+        loop {
+            let mut drained = 0;
+            while drained < max_batch {
+                match rx.try_recv() {
+                    Ok(msg) => {
+                        (msg.func)(&mut state).await;
+                        drained += 1;
+                    }
+                    Err(channel::TryRecvError::Empty) => break,
+                    Err(channel::TryRecvError::Closed) => return,
+                }
+            }
+
+            // Nothing was queued for this tick; wait for the next request
+            // instead of spinning on empty ticks.
+            if drained == 0 {
+                match rx.recv().await {
+                    Ok(msg) => (msg.func)(&mut state).await,
+                    Err(_) => return,
+                }
+            }
+
+            sleep(interval).await;
+        }
+    }
+
+    /// Drops this handle's end of the mailbox and returns immediately.
+    ///
+    /// If other clones of the actor are still alive, the actor keeps
+    /// running; the mailbox only closes once every clone is gone.
+    /// Requests already queued still run to completion in the background.
+    pub fn stop(self) {
+        drop(self.tx);
+    }
+
+    /// Closes the mailbox for every clone of the actor and waits for the
+    /// processor task to drain every already-queued request and exit.
+    ///
+    /// Other clones that are still alive see the mailbox as closed from
+    /// then on, so their `try_*` requests fail with
+    /// [`ActorError::MailboxClosed`].
+    pub async fn shutdown(self) {
+        let handle = self.handle.clone();
+        self.tx.close();
+        let task = handle.lock().unwrap().take();
+        if let Some(task) = task {
+            join(task).await;
+        }
+    }
+
+    /// Returns `true` if the actor's mailbox has been closed, which
+    /// happens once any clone of the actor calls
+    /// [`shutdown`](Self::shutdown), or once the processor task has exited.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Creates a new actor that restarts its processor when a queued
+    /// closure panics, instead of silently dying.
+    ///
+    /// The `factory` builds the initial state, and is called again to
+    /// rebuild it whenever `policy` calls for a fresh restart. Lifecycle
+    /// events are broadcast on the returned receiver so callers can
+    /// observe the actor's health; if nobody is interested, the receiver
+    /// can simply be dropped.
+    pub fn new_supervised<F>(
+        factory: F,
+        policy: RestartPolicy,
+    ) -> (Self, async_broadcast::Receiver<ActorEvent>)
+    where
+        F: Fn() -> S + Send + 'static,
+    {
+        let (tx, rx) = channel::unbounded();
+        let (mut events_tx, events_rx) = async_broadcast::broadcast(16);
+        events_tx.set_overflow(true);
+
+        let handle =
+            spawn_handle(async move { Self::run_supervised(factory, policy, rx, events_tx).await });
+
+        let actor = Self {
+            tx,
+            handle: Arc::new(Mutex::new(Some(handle))),
+        };
+        (actor, events_rx)
+    }
+
+    /// The supervised variant of the command processor.
+    ///
+    /// This wraps each batch of message handling in `catch_unwind` so a
+    /// panicking closure doesn't take the whole actor down with it.
+    async fn run_supervised<F>(
+        factory: F,
+        policy: RestartPolicy,
+        rx: Receiver<Message<S>>,
+        events: async_broadcast::Sender<ActorEvent>,
+    ) where
+        F: Fn() -> S,
+    {
+        let _ = events.try_broadcast(ActorEvent::Started);
+
+        let mut state = factory();
+        let mut attempt: u32 = 0;
+
+        loop {
+            let ran_to_completion = AssertUnwindSafe(async {
+                while let Ok(msg) = rx.recv().await {
+                    (msg.func)(&mut state).await;
+                }
+            })
+            .catch_unwind()
+            .await;
+
+            match ran_to_completion {
+                // The channel closed normally; no more senders remain.
+                Ok(()) => {
+                    let _ = events.try_broadcast(ActorEvent::Stopped);
+                    return;
+                }
+                Err(_) => {
+                    let _ = events.try_broadcast(ActorEvent::Panicked);
+
+                    match &policy {
+                        RestartPolicy::Never => {
+                            let _ = events.try_broadcast(ActorEvent::Stopped);
+                            return;
+                        }
+                        RestartPolicy::Always => {
+                            state = factory();
+                        }
+                        RestartPolicy::OnPanic => {
+                            // `state` survived the panic unwind; keep it as-is.
+                        }
+                        RestartPolicy::ExponentialBackoff { base, max } => {
+                            let delay = base.saturating_mul(1 << attempt.min(16)).min(*max);
+                            attempt += 1;
+                            sleep(delay).await;
+                        }
+                    }
+
+                    let _ = events.try_broadcast(ActorEvent::Restarted);
+                }
+            }
+        }
+    }
+
     /// This is a totally asynchronous operation. Awaiting the returned
     /// future only waits for the operation to be placed in the queue.
     /// It does not wait for the operation to be executed.
@@ -94,22 +472,78 @@ where
         F: for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()>,
         F: 'static + Send,
     {
-        let msg = Message {
-            func: Box::new(move |state| {
-                Box::pin(async move {
-                    f(state).await;
-                })
-            }),
-        };
-
         // TODO: Should we at least log the error?
-        let _ = self.tx.try_send(msg);
+        let _ = self.tx.try_send(Self::wrap(f));
+    }
+
+    /// Like [`cast`](Self::cast), but suspends until there is room in the
+    /// mailbox instead of dropping the request when it's full.
+    pub async fn cast_async<F>(&self, f: F)
+    where
+        F: for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()>,
+        F: 'static + Send,
+    {
+        let _ = self.tx.send(Self::wrap(f)).await;
+    }
+
+    /// Like [`cast`](Self::cast), but returns an [`ActorError`] instead of
+    /// silently dropping the request when the mailbox is full or closed.
+    pub fn try_cast<F>(&self, f: F) -> Result<(), ActorError>
+    where
+        F: for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()>,
+        F: 'static + Send,
+    {
+        self.tx.try_send(Self::wrap(f)).map_err(|e| match e {
+            channel::TrySendError::Full(_) => ActorError::Full,
+            channel::TrySendError::Closed(_) => ActorError::MailboxClosed,
+        })
+    }
+
+    /// Casts `f` into the actor once, after `delay` has elapsed.
+    pub fn send_later<F>(&self, delay: Duration, f: F)
+    where
+        F: for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()>,
+        F: 'static + Send,
+    {
+        let actor = self.clone();
+        spawn(async move {
+            sleep(delay).await;
+            actor.cast(f);
+        });
+    }
+
+    /// Casts `f` into the actor repeatedly, once every `period`.
+    ///
+    /// Returns an [`IntervalHandle`] that stops the timer when dropped or
+    /// when [`cancel`](IntervalHandle::cancel) is called.
+    pub fn send_interval<F>(&self, period: Duration, f: F) -> IntervalHandle
+    where
+        F: for<'a> FnOnce(&'a mut S) -> BoxFuture<'a, ()>,
+        F: Clone + 'static + Send,
+    {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        spawn(run_interval(self.clone(), period, f, cancelled.clone()));
+        IntervalHandle { cancelled }
     }
 
     /// A call is a synchronous operation within the async task.
     /// It will queue the request, wait for it to execute, and
     /// return the result.
+    ///
+    /// Panics if the actor is gone before it responds. Use
+    /// [`try_call`](Self::try_call) to observe that instead of panicking.
     pub async fn call<F, R>(&self, f: F) -> R
+    where
+        F: for<'a> FnOnce(Sender<R>, &'a mut S) -> BoxFuture<'a, Option<R>>,
+        F: 'static + Send,
+        R: 'static + Send + Debug,
+    {
+        self.try_call(f).await.expect("Actor is gone")
+    }
+
+    /// Like [`call`](Self::call), but returns an [`ActorError`] instead of
+    /// panicking if the actor is gone before it responds.
+    pub async fn try_call<F, R>(&self, f: F) -> Result<R, ActorError>
     where
         F: for<'a> FnOnce(Sender<R>, &'a mut S) -> BoxFuture<'a, Option<R>>,
         F: 'static + Send,
@@ -126,9 +560,18 @@ where
             }),
         };
 
-        let _ = self.tx.send(msg).await;
-        // TODO: Return an error instead of panicking
-        rx.recv().await.expect("Actor is gone")
+        self.tx
+            .send(msg)
+            .await
+            .map_err(|_| ActorError::MailboxClosed)?;
+
+        rx.recv().await.map_err(|_| {
+            if self.is_closed() {
+                ActorError::Stopped
+            } else {
+                ActorError::NoResponse
+            }
+        })
     }
 
     /// Blocks the calling task until all requests up to this point have
@@ -141,6 +584,26 @@ where
     pub async fn flush(&self) {
         self.call(|_, _| Box::pin(async move { Some(()) })).await
     }
+
+    /// Registers this actor under `name` in the process-wide [`Registry`],
+    /// so it can be found later with `Registry::lookup`.
+    pub fn register(&self, name: impl Into<String>) {
+        Registry::register(name, self.clone());
+    }
+
+    /// Subscribes this actor to messages of type `M` published through the
+    /// [`Broker`], requiring the state to implement [`Handle<M>`](Handle)
+    /// so the broker knows how to apply the message.
+    pub fn subscribe<M>(&self)
+    where
+        S: Handle<M>,
+        M: Send + 'static,
+    {
+        let actor = self.clone();
+        Broker::subscribe::<M>(Box::new(move |msg: M| {
+            actor.cast(move |state| state.handle(msg));
+        }));
+    }
 }
 
 impl<S> Default for Actor<S>
@@ -152,3 +615,211 @@ where
         Self::new(S::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supervised_actor_restarts_after_panic() {
+        futures::executor::block_on(async {
+            let (actor, mut events) = Actor::new_supervised(|| 0i32, RestartPolicy::Always);
+
+            // This panics while being processed; the processor should
+            // catch it and restart with fresh state instead of dying.
+            actor.cast(|_state| {
+                Box::pin(async move {
+                    panic!("boom");
+                })
+            });
+
+            let n = actor
+                .call(|_, state| {
+                    Box::pin(async move {
+                        *state += 1;
+                        Some(*state)
+                    })
+                })
+                .await;
+
+            // `Always` rebuilds the state from the factory, so the count
+            // starts over at 0 rather than carrying on from a corrupted
+            // state.
+            assert_eq!(n, 1);
+
+            let mut seen = Vec::new();
+            while let Ok(ev) = events.try_recv() {
+                seen.push(ev);
+            }
+            assert!(seen.contains(&ActorEvent::Panicked));
+            assert!(seen.contains(&ActorEvent::Restarted));
+        });
+    }
+
+    #[test]
+    fn stop_does_not_close_other_clones_mailbox() {
+        futures::executor::block_on(async {
+            let a = Actor::new(0i32);
+            let b = a.clone();
+
+            a.stop();
+
+            // `b` still holds a sender, so the mailbox must still be open
+            // and usable through it.
+            assert!(!b.is_closed());
+            let n = b
+                .call(|_, state| {
+                    Box::pin(async move {
+                        *state += 1;
+                        Some(*state)
+                    })
+                })
+                .await;
+            assert_eq!(n, 1);
+        });
+    }
+
+    #[test]
+    fn shutdown_waits_for_queued_requests_to_drain() {
+        futures::executor::block_on(async {
+            use std::sync::atomic::AtomicUsize;
+
+            let counter = Arc::new(AtomicUsize::new(0));
+            let actor = Actor::new(());
+
+            for _ in 0..5 {
+                let counter = counter.clone();
+                actor.cast(move |_| {
+                    Box::pin(async move {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    })
+                });
+            }
+
+            actor.shutdown().await;
+            assert_eq!(counter.load(Ordering::SeqCst), 5);
+        });
+    }
+
+    #[test]
+    fn bounded_mailbox_applies_backpressure() {
+        futures::executor::block_on(async {
+            let actor = Actor::with_capacity((), 1);
+
+            // Blocks the processor on `gate` once it's dequeued this
+            // request, so the mailbox's single slot is free again but the
+            // processor itself is busy and won't drain anything else.
+            let (gate_tx, gate_rx) = channel::bounded::<()>(1);
+            actor.cast(move |_| {
+                Box::pin(async move {
+                    let _ = gate_rx.recv().await;
+                })
+            });
+
+            // Give the processor a moment to dequeue the request above.
+            sleep(Duration::from_millis(20)).await;
+
+            // Fills the now-empty single slot; the processor won't touch it
+            // until `gate` is released.
+            actor.try_cast(|_| Box::pin(async {})).unwrap();
+
+            // The slot is occupied and the processor is busy, so this one
+            // has nowhere to go.
+            assert_eq!(
+                actor.try_cast(|_| Box::pin(async {})),
+                Err(ActorError::Full)
+            );
+
+            let _ = gate_tx.try_send(());
+        });
+    }
+
+    #[test]
+    fn send_later_delivers_after_delay() {
+        futures::executor::block_on(async {
+            let actor = Actor::new(0i32);
+
+            actor.send_later(Duration::from_millis(10), |state| {
+                Box::pin(async move {
+                    *state = 42;
+                })
+            });
+
+            // `call` queues behind the delayed `cast`, so it only sees 42
+            // once the timer has fired and the delayed request has run.
+            sleep(Duration::from_millis(100)).await;
+            let n = actor
+                .call(|_, state| Box::pin(async move { Some(*state) }))
+                .await;
+            assert_eq!(n, 42);
+        });
+    }
+
+    #[test]
+    fn send_interval_ticks_until_cancelled() {
+        futures::executor::block_on(async {
+            use std::sync::atomic::AtomicUsize;
+
+            let counter = Arc::new(AtomicUsize::new(0));
+            let actor = Actor::new(());
+
+            let handle = {
+                let counter = counter.clone();
+                actor.send_interval(Duration::from_millis(10), move |_| {
+                    let counter = counter.clone();
+                    Box::pin(async move {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    })
+                })
+            };
+
+            sleep(Duration::from_millis(55)).await;
+            handle.cancel();
+            let seen_at_cancel = counter.load(Ordering::SeqCst);
+            assert!(seen_at_cancel >= 2);
+
+            sleep(Duration::from_millis(55)).await;
+            assert_eq!(counter.load(Ordering::SeqCst), seen_at_cancel);
+        });
+    }
+
+    #[test]
+    fn try_call_and_try_cast_report_mailbox_closed_after_shutdown() {
+        futures::executor::block_on(async {
+            let actor = Actor::new(());
+            let other = actor.clone();
+            actor.shutdown().await;
+
+            assert_eq!(
+                other.try_cast(|_| Box::pin(async {})),
+                Err(ActorError::MailboxClosed)
+            );
+            assert_eq!(
+                other.try_call(|_, _| Box::pin(async { Some(()) })).await,
+                Err(ActorError::MailboxClosed)
+            );
+        });
+    }
+
+    #[test]
+    fn throttled_actor_drains_in_batches() {
+        futures::executor::block_on(async {
+            use std::sync::atomic::AtomicUsize;
+
+            let counter = Arc::new(AtomicUsize::new(0));
+            let actor = Actor::with_throttle((), Duration::from_millis(20), 3);
+
+            for _ in 0..7 {
+                let counter = counter.clone();
+                actor.cast(move |_| {
+                    Box::pin(async move {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    })
+                });
+            }
+
+            actor.flush().await;
+            assert_eq!(counter.load(Ordering::SeqCst), 7);
+        });
+    }
+}
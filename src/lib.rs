@@ -13,7 +13,11 @@
 //! cooper
 
 mod actor;
+mod local_actor;
+mod registry;
 mod threaded_actor;
 
 pub use actor::*;
+pub use local_actor::*;
+pub use registry::*;
 pub use threaded_actor::*;
@@ -0,0 +1,242 @@
+// cooper/src/local_actor.rs
+//
+// This file is part of the `cooper-rs` library.
+//
+// Copyright (c) 2021, Frank Pagliughi <fpagliughi@mindspring.com>
+// All Rights Reserved
+//
+// Licensed under the MIT license:
+//   <LICENSE or http://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed except according
+// to those terms.
+//
+//! cooper
+
+use crate::ActorError;
+use async_channel::{self as channel, Receiver, Sender};
+use futures::future::LocalBoxFuture;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::thread;
+
+/// The local actor function signature.
+///
+/// Unlike [`BoxedActorFn`](crate::BoxedActorFn), the returned future isn't
+/// required to be `Send`, since it only ever runs on the thread that owns
+/// `S`. The closure itself still has to be `Send` (see [`cast`](LocalActor::cast)
+/// /[`call`](LocalActor::call)), since it's built on the caller's thread and
+/// has to cross over to the actor's dedicated thread through the mailbox.
+pub type BoxedLocalActorFn<S> = Box<dyn for<'a> FnOnce(&'a mut S) -> LocalBoxFuture<'a, ()>>;
+
+/// Message type for the `LocalActor`.
+///
+/// This wraps a closure over `&mut S`, the same way `Actor`'s internal
+/// message type wraps one over `S` directly.
+struct Message<S> {
+    func: BoxedLocalActorFn<S>,
+}
+
+// SAFETY: `Box<dyn FnOnce(&mut S) -> LocalBoxFuture<_>>` only auto-implements
+// `Send` when it's explicitly declared `+ Send`, which `BoxedLocalActorFn`
+// isn't (its signature mentions `S`, which may be `!Send`). But `S` itself
+// never moves through a `Message` - it's built once by `factory` on the
+// actor's dedicated thread and only ever handed out as `&mut S` to whatever
+// closure is currently running there. The only thing that actually crosses
+// threads inside a `Message` is the closure's own captured environment, and
+// `cast`/`call`/`try_call` all require `F: Send` for exactly that reason.
+// That makes it sound to assert `Send` for the wrapper here.
+unsafe impl<S> Send for Message<S> {}
+
+/// A single-threaded counterpart to [`Actor`](crate::Actor), for state that
+/// can't be `Send`.
+///
+/// `Rc`-backed caches, `!Send` FFI handles, and single-threaded GUI or
+/// database clients can all be managed behind a `LocalActor` instead.
+/// Internally, the state lives on a dedicated thread that drives its own
+/// single-threaded executor - a `tokio::task::LocalSet` under the `tokio`
+/// feature, or a `smol::LocalExecutor` otherwise - and the actor is reached
+/// through the same kind of mailbox as `Actor`.
+///
+/// `LocalActor` is not `Send` or `Clone`; a handle must stay on the thread
+/// that created it. Otherwise it mirrors `Actor`'s `cast`/`call`/`flush`
+/// API.
+pub struct LocalActor<S> {
+    /// The channel to send requests to the actor's dedicated thread.
+    tx: Sender<Message<S>>,
+    /// `*const ()` is neither `Send` nor `Sync`, which keeps `LocalActor`
+    /// itself from being moved to, or shared with, another thread. That's
+    /// what the safety of `Message`'s forced `Send` impl actually depends
+    /// on: without this, the compiler would happily auto-derive `Send`
+    /// for `LocalActor` and let a handle travel to a second thread, from
+    /// where a `!Send`-capturing closure could be built and shipped back
+    /// across the mailbox.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl<S> LocalActor<S>
+where
+    S: 'static,
+{
+    /// Creates a new local actor, building its state from `factory` on the
+    /// dedicated thread that will run it.
+    ///
+    /// `factory` itself must be `Send`, so it can be handed to the new
+    /// thread, even though the `S` it produces need not be.
+    pub fn new<F>(factory: F) -> Self
+    where
+        F: FnOnce() -> S + Send + 'static,
+    {
+        let (tx, rx) = channel::unbounded();
+        spawn_local_thread(factory, rx);
+        Self {
+            tx,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// This is a totally asynchronous operation. It only places the
+    /// operation in the queue and returns immediately; it does not wait
+    /// for the operation to be executed.
+    pub fn cast<F>(&self, f: F)
+    where
+        F: for<'a> FnOnce(&'a mut S) -> LocalBoxFuture<'a, ()> + Send + 'static,
+    {
+        let msg = Message {
+            func: Box::new(move |state| {
+                Box::pin(async move {
+                    f(state).await;
+                })
+            }),
+        };
+
+        let _ = self.tx.try_send(msg);
+    }
+
+    /// A call is a synchronous operation within the async task.
+    /// It will queue the request, wait for it to execute, and
+    /// return the result.
+    ///
+    /// Panics if the actor is gone before it responds. Use
+    /// [`try_call`](Self::try_call) to observe that instead of panicking.
+    pub async fn call<F, R>(&self, f: F) -> R
+    where
+        F: for<'a> FnOnce(Sender<R>, &'a mut S) -> LocalBoxFuture<'a, Option<R>> + Send + 'static,
+        R: 'static + Debug,
+    {
+        self.try_call(f).await.expect("Actor is gone")
+    }
+
+    /// Like [`call`](Self::call), but returns an [`ActorError`] instead of
+    /// panicking if the actor is gone before it responds.
+    pub async fn try_call<F, R>(&self, f: F) -> Result<R, ActorError>
+    where
+        F: for<'a> FnOnce(Sender<R>, &'a mut S) -> LocalBoxFuture<'a, Option<R>> + Send + 'static,
+        R: 'static + Debug,
+    {
+        let (tx, rx) = channel::bounded(1);
+        let msg = Message {
+            func: Box::new(move |state| {
+                Box::pin(async move {
+                    if let Some(res) = f(tx.clone(), state).await {
+                        let _ = tx.send(res).await;
+                    }
+                })
+            }),
+        };
+
+        self.tx
+            .send(msg)
+            .await
+            .map_err(|_| ActorError::MailboxClosed)?;
+
+        rx.recv().await.map_err(|_| {
+            if self.tx.is_closed() {
+                ActorError::Stopped
+            } else {
+                ActorError::NoResponse
+            }
+        })
+    }
+
+    /// Blocks the calling task until all requests queued before this one
+    /// have been processed.
+    pub async fn flush(&self) {
+        self.call(|_, _| Box::pin(async move { Some(()) })).await
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+fn spawn_local_thread<S, F>(factory: F, rx: Receiver<Message<S>>)
+where
+    S: 'static,
+    F: FnOnce() -> S + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut state = factory();
+        let local_ex = smol::LocalExecutor::new();
+
+        futures::executor::block_on(local_ex.run(async {
+            while let Ok(msg) = rx.recv().await {
+                (msg.func)(&mut state).await;
+            }
+        }));
+    });
+}
+
+#[cfg(feature = "tokio")]
+fn spawn_local_thread<S, F>(factory: F, rx: Receiver<Message<S>>)
+where
+    S: 'static,
+    F: FnOnce() -> S + Send + 'static,
+{
+    thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start the local actor's runtime");
+        let local = tokio::task::LocalSet::new();
+
+        local.block_on(&rt, async move {
+            let mut state = factory();
+            tokio::task::spawn_local(async move {
+                while let Ok(msg) = rx.recv().await {
+                    (msg.func)(&mut state).await;
+                }
+            })
+            .await
+            .expect("local actor task panicked");
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn call_runs_on_the_dedicated_thread_against_not_send_state() {
+        futures::executor::block_on(async {
+            // `Rc<Cell<i32>>` is `!Send`; `LocalActor` must be able to hold
+            // it as state and still let `cast`/`call` reach it from this
+            // (different) thread.
+            let actor = LocalActor::new(|| Rc::new(Cell::new(0)));
+
+            actor.cast(|state| {
+                let state = state.clone();
+                Box::pin(async move {
+                    state.set(state.get() + 1);
+                })
+            });
+
+            let n = actor
+                .call(|_, state| {
+                    let state = state.clone();
+                    Box::pin(async move { Some(state.get()) })
+                })
+                .await;
+            assert_eq!(n, 1);
+        });
+    }
+}
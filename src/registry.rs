@@ -0,0 +1,169 @@
+// cooper/src/registry.rs
+//
+// This file is part of the `cooper-rs` library.
+//
+// Copyright (c) 2021, Frank Pagliughi <fpagliughi@mindspring.com>
+// All Rights Reserved
+//
+// Licensed under the MIT license:
+//   <LICENSE or http://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed except according
+// to those terms.
+//
+//! cooper
+
+use crate::Actor;
+use futures::future::BoxFuture;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+type NamedActors = HashMap<(TypeId, String), Box<dyn Any + Send>>;
+
+static ACTORS: OnceLock<Mutex<NamedActors>> = OnceLock::new();
+
+fn actors() -> &'static Mutex<NamedActors> {
+    ACTORS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// An opt-in, process-wide registry of named actors.
+///
+/// Actors register themselves under a name with [`Registry::register`]
+/// (or the [`Actor::register`] shorthand), so that other parts of the
+/// program can find them with [`Registry::lookup`] instead of threading a
+/// handle through every constructor.
+pub struct Registry;
+
+impl Registry {
+    /// Registers `actor` under `name`.
+    ///
+    /// Replaces any other actor of the same state type previously
+    /// registered under that name.
+    pub fn register<S>(name: impl Into<String>, actor: Actor<S>)
+    where
+        S: Send + 'static,
+    {
+        let mut actors = actors().lock().unwrap();
+        actors.insert((TypeId::of::<S>(), name.into()), Box::new(actor));
+    }
+
+    /// Looks up a previously registered actor by name.
+    ///
+    /// Returns `None` if no actor with state type `S` was registered
+    /// under `name`.
+    pub fn lookup<S>(name: &str) -> Option<Actor<S>>
+    where
+        S: Send + 'static,
+    {
+        let actors = actors().lock().unwrap();
+        actors
+            .get(&(TypeId::of::<S>(), name.to_string()))
+            .and_then(|actor| actor.downcast_ref::<Actor<S>>())
+            .cloned()
+    }
+}
+
+/// Implemented by actor state that can handle a message of type `M`
+/// published through the [`Broker`].
+pub trait Handle<M>: Send + 'static {
+    /// Applies `msg` to the state.
+    fn handle(&mut self, msg: M) -> BoxFuture<'_, ()>;
+}
+
+type Subscribers<M> = Vec<Box<dyn Fn(M) + Send + Sync>>;
+
+static TOPICS: OnceLock<Mutex<HashMap<TypeId, Box<dyn Any + Send>>>> = OnceLock::new();
+
+fn topics() -> &'static Mutex<HashMap<TypeId, Box<dyn Any + Send>>> {
+    TOPICS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// An opt-in, process-wide pub/sub broker layered on top of actor
+/// mailboxes.
+///
+/// Actors subscribe to a message type with [`Actor::subscribe`], and any
+/// part of the program can fan a message out to every subscriber with
+/// [`Broker::publish`], without holding a handle to any of them.
+pub struct Broker;
+
+impl Broker {
+    /// Publishes `msg` to every actor currently subscribed to messages of
+    /// type `M`, via each actor's own `cast`.
+    pub fn publish<M>(msg: M)
+    where
+        M: Clone + Send + 'static,
+    {
+        let topics = topics().lock().unwrap();
+        if let Some(subs) = topics
+            .get(&TypeId::of::<M>())
+            .and_then(|subs| subs.downcast_ref::<Subscribers<M>>())
+        {
+            for subscriber in subs {
+                subscriber(msg.clone());
+            }
+        }
+    }
+
+    /// Registers a type-erased subscriber for messages of type `M`.
+    ///
+    /// Used by [`Actor::subscribe`] to wire an actor's `cast` into the
+    /// topic for `M`.
+    pub(crate) fn subscribe<M>(subscriber: Box<dyn Fn(M) + Send + Sync>)
+    where
+        M: Send + 'static,
+    {
+        let mut topics = topics().lock().unwrap();
+        let subs = topics
+            .entry(TypeId::of::<M>())
+            .or_insert_with(|| Box::new(Subscribers::<M>::new()))
+            .downcast_mut::<Subscribers<M>>()
+            .expect("topic registered under a mismatched message type");
+        subs.push(subscriber);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Actor;
+
+    #[test]
+    fn register_and_lookup_roundtrip_by_name_and_type() {
+        struct RegistryTestState(i32);
+
+        let actor = Actor::new(RegistryTestState(7));
+        Registry::register("registry-test-actor", actor.clone());
+
+        assert!(Registry::lookup::<RegistryTestState>("registry-test-actor").is_some());
+        assert!(Registry::lookup::<RegistryTestState>("no-such-actor").is_none());
+    }
+
+    #[test]
+    fn broker_publishes_to_every_subscribed_actor() {
+        #[derive(Clone)]
+        struct BrokerTestEvent(i32);
+
+        struct BrokerTestState {
+            sum: i32,
+        }
+
+        impl Handle<BrokerTestEvent> for BrokerTestState {
+            fn handle(&mut self, msg: BrokerTestEvent) -> BoxFuture<'_, ()> {
+                self.sum += msg.0;
+                Box::pin(async {})
+            }
+        }
+
+        futures::executor::block_on(async {
+            let actor = Actor::new(BrokerTestState { sum: 0 });
+            actor.subscribe::<BrokerTestEvent>();
+
+            Broker::publish(BrokerTestEvent(5));
+
+            let sum = actor
+                .call(|_, state| Box::pin(async move { Some(state.sum) }))
+                .await;
+            assert_eq!(sum, 5);
+        });
+    }
+}
@@ -10,7 +10,10 @@
 // This file may not be copied, modified, or distributed except according
 // to those terms.
 
+use crate::ActorError;
 use crossbeam_channel::{self as channel, Receiver, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 
 /// The type of function that can be sent to a `ThreadedActor<T>`.
@@ -37,6 +40,10 @@ type QueueTask<T> = BoxedTask<T, ()>;
 pub struct ThreadedActor<T> {
     /// A transmit channel to send requests to the actor thread.
     tx: Sender<QueueTask<T>>,
+    /// The join handle for the actor thread, so it can be joined on
+    /// shutdown. Shared across clones since only one of them needs to
+    /// claim it.
+    handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
 }
 
 impl<T> ThreadedActor<T>
@@ -44,14 +51,34 @@ where
     T: Send + 'static,
 {
     /// Creates a threaded actor with the specified initial state.
+    ///
+    /// The actor's mailbox is unbounded, so a producer that outpaces the
+    /// actor can grow it without limit. Use [`with_capacity`](Self::with_capacity)
+    /// to bound it instead.
     pub fn new(state: T) -> Self {
         let (tx, rx) = channel::unbounded();
+        Self::spawn_with(state, tx, rx)
+    }
 
-        thread::spawn(move || {
+    /// Creates a threaded actor with the specified initial state, with a
+    /// bounded mailbox.
+    ///
+    /// Once `cap` requests are queued, `cast`/`call` block the caller until
+    /// the actor thread drains the backlog.
+    pub fn with_capacity(state: T, cap: usize) -> Self {
+        let (tx, rx) = channel::bounded(cap);
+        Self::spawn_with(state, tx, rx)
+    }
+
+    fn spawn_with(state: T, tx: Sender<QueueTask<T>>, rx: Receiver<QueueTask<T>>) -> Self {
+        let handle = thread::spawn(move || {
             Self::thr_func(state, rx);
         });
 
-        Self { tx }
+        Self {
+            tx,
+            handle: Arc::new(Mutex::new(Some(handle))),
+        }
     }
 
     /// The thread function for the actor.
@@ -73,25 +100,60 @@ where
         self.tx.send(Box::new(f)).unwrap();
     }
 
+    /// Like [`cast`](Self::cast), but returns an [`ActorError`] instead of
+    /// blocking the caller when the mailbox is full, or panicking when
+    /// it's closed.
+    pub fn try_cast<F>(&self, f: F) -> Result<(), ActorError>
+    where
+        F: FnOnce(&mut T) -> () + Send + 'static,
+    {
+        self.tx.try_send(Box::new(f)).map_err(|e| match e {
+            channel::TrySendError::Full(_) => ActorError::Full,
+            channel::TrySendError::Disconnected(_) => ActorError::MailboxClosed,
+        })
+    }
+
     /// Sends a synchronous request to the actor.
     ///
     /// This queues the request to the actor thread, then blocks waiting for
     /// a response.
+    ///
+    /// Panics if the actor is gone before it responds. Use
+    /// [`try_call`](Self::try_call) to observe that instead of panicking.
     pub fn call<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(Sender<R>, &mut T) -> Option<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        self.try_call(f).expect("Actor is gone")
+    }
+
+    /// Like [`call`](Self::call), but returns an [`ActorError`] instead of
+    /// panicking if the actor is gone before it responds.
+    pub fn try_call<F, R>(&self, f: F) -> Result<R, ActorError>
     where
         F: FnOnce(Sender<R>, &mut T) -> Option<R> + Send + 'static,
         R: Send + 'static,
     {
         let (tx, rx) = channel::unbounded();
+        let ran = Arc::new(AtomicBool::new(false));
+        let ran_flag = ran.clone();
         self.tx
             .send(Box::new(move |val: &mut T| {
                 if let Some(res) = f(tx.clone(), val) {
-                    tx.send(res).unwrap();
+                    let _ = tx.send(res);
                 }
+                ran_flag.store(true, Ordering::Release);
             }))
-            .unwrap();
+            .map_err(|_| ActorError::MailboxClosed)?;
 
-        rx.recv().unwrap()
+        rx.recv().map_err(|_| {
+            if ran.load(Ordering::Acquire) {
+                ActorError::NoResponse
+            } else {
+                ActorError::Stopped
+            }
+        })
     }
 
     /// Blocks the calling task until all requests up to this point have
@@ -104,6 +166,29 @@ where
     pub fn flush(&self) {
         self.call(move |_, _| Some(()));
     }
+
+    /// Drops this handle's end of the mailbox and returns immediately.
+    ///
+    /// If other clones of the actor are still alive, the actor thread
+    /// keeps running; the channel only closes once every clone is gone.
+    pub fn stop(self) {
+        drop(self.tx);
+    }
+
+    /// Drops this handle's end of the mailbox and blocks until the actor
+    /// thread has drained every already-queued request and exited.
+    ///
+    /// Note that this blocks forever if another clone of the actor is
+    /// still alive, since the channel won't close until every sender is
+    /// dropped.
+    pub fn shutdown(self) {
+        let handle = self.handle.clone();
+        drop(self.tx);
+        let task = handle.lock().unwrap().take();
+        if let Some(task) = task {
+            let _ = task.join();
+        }
+    }
 }
 
 impl<T> Default for ThreadedActor<T>